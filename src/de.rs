@@ -1,7 +1,6 @@
 use crate::error::Direction;
 use crate::{Error, ErrorCode, Result};
 use serde::de;
-use serde::de::Error as DeError;
 
 pub struct Deserializer<'de> {
     pub(crate) input: &'de str,
@@ -16,144 +15,259 @@ impl<'de> Deserializer<'de> {
 impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     type Error = Error;
 
-    fn deserialize_bool<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "bool".to_owned(),
-        )))
+        let value = match self.input {
+            "true" => true,
+            "false" => false,
+            _ => {
+                return Err(ErrorCode::InvalidType {
+                    unexpected: self.input.to_owned(),
+                    expected: "a boolean".to_owned(),
+                }
+                .into())
+            }
+        };
+        self.input = "";
+        visitor.visit_bool(value)
     }
 
-    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "any".to_owned(),
-        )))
+        match self.input {
+            "true" => {
+                self.input = "";
+                visitor.visit_bool(true)
+            }
+            "false" => {
+                self.input = "";
+                visitor.visit_bool(false)
+            }
+            _ => {
+                if let Ok(value) = self.input.parse::<i64>() {
+                    self.input = "";
+                    visitor.visit_i64(value)
+                } else if let Ok(value) = self.input.parse::<u64>() {
+                    self.input = "";
+                    visitor.visit_u64(value)
+                } else if let Ok(value) = self.input.parse::<f64>() {
+                    self.input = "";
+                    visitor.visit_f64(value)
+                } else {
+                    let value = self.input;
+                    self.input = "";
+                    visitor.visit_borrowed_str(value)
+                }
+            }
+        }
     }
 
-    fn deserialize_i8<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "i8".to_owned(),
-        )))
+        match self.input.parse::<i8>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_i64(value as i64)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "i8".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_i16<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "i16".to_owned(),
-        )))
+        match self.input.parse::<i16>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_i64(value as i64)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "i16".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_i32<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "i32".to_owned(),
-        )))
+        match self.input.parse::<i32>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_i64(value as i64)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "i32".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_i64<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "i64".to_owned(),
-        )))
+        match self.input.parse::<i64>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_i64(value)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "i64".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_i128<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "i128".to_owned(),
-        )))
+        match self.input.parse::<i128>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_i128(value)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "i128".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_u8<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "u8".to_owned(),
-        )))
+        match self.input.parse::<u8>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_u64(value as u64)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "u8".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_u16<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "u16".to_owned(),
-        )))
+        match self.input.parse::<u16>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_u64(value as u64)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "u16".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_u32<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "u32".to_owned(),
-        )))
+        match self.input.parse::<u32>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_u64(value as u64)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "u32".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_u64<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "u64".to_owned(),
-        )))
+        match self.input.parse::<u64>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_u64(value)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "u64".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_u128<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "u128".to_owned(),
-        )))
+        match self.input.parse::<u128>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_u128(value)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "u128".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_f32<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "f32".to_owned(),
-        )))
+        match self.input.parse::<f32>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_f64(value as f64)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "f32".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_f64<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "f64".to_owned(),
-        )))
+        match self.input.parse::<f64>() {
+            Ok(value) => {
+                self.input = "";
+                visitor.visit_f64(value)
+            }
+            Err(_) => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "f64".to_owned(),
+            }
+            .into()),
+        }
     }
 
     fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
@@ -163,17 +277,25 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         visitor.visit_unit()
     }
 
-    fn deserialize_char<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "char".to_owned(),
-        )))
+        let mut chars = self.input.chars();
+        match (chars.next(), chars.next()) {
+            (Some(value), None) => {
+                self.input = "";
+                visitor.visit_char(value)
+            }
+            _ => Err(ErrorCode::InvalidType {
+                unexpected: self.input.to_owned(),
+                expected: "a single character".to_owned(),
+            }
+            .into()),
+        }
     }
 
-    fn deserialize_str<V>(mut self, visitor: V) -> Result<V::Value>
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
@@ -296,21 +418,23 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_enum(VariantName::new(self))
+        if self.input.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
     }
 
     fn is_human_readable(&self) -> bool {
         true
     }
 
-    fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        Err(Error::from(ErrorCode::UnsupportedOperation(
-            Direction::Deserialization,
-            "any".to_owned(),
-        )))
+        self.input = "";
+        visitor.visit_unit()
     }
 
     fn deserialize_newtype_struct<V>(self, _name: &'static str, _visitor: V) -> Result<V::Value>
@@ -326,16 +450,103 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     fn deserialize_enum<V>(
         self,
         _name: &'static str,
-        variants: &'static [&'static str],
+        _variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        let variant = self.input.to_owned();
-        visitor
-            .visit_enum(VariantName::new(self))
-            .map_err(|_| Error::unknown_variant(&variant, variants))
+        visitor.visit_enum(VariantName::new(self))
+    }
+}
+
+/// Forward a `de::Deserializer` method that takes `self` by value to
+/// the existing `&mut Deserializer` implementation above. Used to
+/// build the owned `Deserializer` impl below without hand-copying
+/// every method signature. Only the methods listed in that impl are
+/// forwarded; any other provided method of `serde::Deserializer`
+/// keeps its default implementation there.
+macro_rules! forward_owned_deserializer_method {
+    ($name:ident $(, $arg:ident : $ty:ty)*) => {
+        fn $name<V>(mut self, $($arg: $ty,)* visitor: V) -> Result<V::Value>
+        where
+            V: de::Visitor<'de>,
+        {
+            (&mut self).$name($($arg,)* visitor)
+        }
+    };
+}
+
+/// An owned counterpart to the `&mut Deserializer` impl above, which
+/// merely forwards every method to it. This exists so that a
+/// `Deserializer` can be handed to APIs that expect to take
+/// ownership of a deserializer, such as [`de::IntoDeserializer`].
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    forward_owned_deserializer_method!(deserialize_bool);
+    forward_owned_deserializer_method!(deserialize_any);
+    forward_owned_deserializer_method!(deserialize_i8);
+    forward_owned_deserializer_method!(deserialize_i16);
+    forward_owned_deserializer_method!(deserialize_i32);
+    forward_owned_deserializer_method!(deserialize_i64);
+    forward_owned_deserializer_method!(deserialize_i128);
+    forward_owned_deserializer_method!(deserialize_u8);
+    forward_owned_deserializer_method!(deserialize_u16);
+    forward_owned_deserializer_method!(deserialize_u32);
+    forward_owned_deserializer_method!(deserialize_u64);
+    forward_owned_deserializer_method!(deserialize_u128);
+    forward_owned_deserializer_method!(deserialize_f32);
+    forward_owned_deserializer_method!(deserialize_f64);
+    forward_owned_deserializer_method!(deserialize_unit);
+    forward_owned_deserializer_method!(deserialize_char);
+    forward_owned_deserializer_method!(deserialize_str);
+    forward_owned_deserializer_method!(deserialize_string);
+    forward_owned_deserializer_method!(deserialize_bytes);
+    forward_owned_deserializer_method!(deserialize_byte_buf);
+    forward_owned_deserializer_method!(deserialize_identifier);
+    forward_owned_deserializer_method!(deserialize_tuple, len: usize);
+    forward_owned_deserializer_method!(
+        deserialize_struct,
+        name: &'static str,
+        fields: &'static [&'static str]
+    );
+    forward_owned_deserializer_method!(deserialize_unit_struct, name: &'static str);
+    forward_owned_deserializer_method!(deserialize_tuple_struct, name: &'static str, len: usize);
+    forward_owned_deserializer_method!(deserialize_map);
+    forward_owned_deserializer_method!(deserialize_seq);
+    forward_owned_deserializer_method!(deserialize_option);
+    forward_owned_deserializer_method!(deserialize_ignored_any);
+    forward_owned_deserializer_method!(deserialize_newtype_struct, name: &'static str);
+    forward_owned_deserializer_method!(
+        deserialize_enum,
+        name: &'static str,
+        variants: &'static [&'static str]
+    );
+
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+}
+
+/// Allow a `Deserializer` to be used wherever a type generic over
+/// `IntoDeserializer` is expected, e.g. when deserializing the keys
+/// of a map from a collection of variant-name strings.
+impl<'de> de::IntoDeserializer<'de, Error> for Deserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+/// The borrowing counterpart of the above, for callers that already
+/// hold a `&mut Deserializer`.
+impl<'de> de::IntoDeserializer<'de, Error> for &mut Deserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
     }
 }
 
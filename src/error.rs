@@ -19,14 +19,12 @@ impl From<ErrorCode> for Error {
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub(crate) enum Direction {
-    Serialization,
     Deserialization,
 }
 
 impl fmt::Display for Direction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Direction::Serialization => f.write_str("serialization"),
             Direction::Deserialization => f.write_str("deserialization"),
         }
     }
@@ -44,7 +42,7 @@ pub(crate) enum ErrorCode {
         received: String,
         allowed: Vec<String>,
     },
-    TrailingCharacters,
+    TrailingData(String),
 }
 
 impl fmt::Display for ErrorCode {
@@ -65,10 +63,9 @@ impl fmt::Display for ErrorCode {
                     received, allowed
                 )
             }
-            ErrorCode::TrailingCharacters => write!(
-                f,
-                "trailing characters: input ends with trailing characters"
-            ),
+            ErrorCode::TrailingData(remaining) => {
+                write!(f, "trailing data: {} was not consumed", remaining)
+            }
         }
     }
 }
@@ -1,18 +1,27 @@
 // Copyright (C) 2020-2024 Daniel Mueller <deso@posteo.net>
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
-use std::error::Error;
+mod de;
+mod error;
+
+use std::error::Error as StdError;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::result::Result as StdResult;
 
 use serde::ser::Error as SerdeError;
 use serde::ser::Impossible;
 use serde::ser::SerializeStructVariant;
 use serde::ser::SerializeTupleVariant;
+use serde::Deserialize;
 use serde::Serialize;
 use serde::Serializer as SerdeSerializer;
 
+pub use crate::error::Error;
+pub use crate::error::Result;
+pub(crate) use crate::error::ErrorCode;
+
 
 /// An error emitted when attempting to perform an unsupported
 /// operation.
@@ -29,7 +38,7 @@ impl Display for UnsupportedType {
   }
 }
 
-impl Error for UnsupportedType {}
+impl StdError for UnsupportedType {}
 
 impl SerdeError for UnsupportedType {
   #[inline]
@@ -49,7 +58,7 @@ impl SerdeError for UnsupportedType {
 /// Note that only enum variants may be converted here and all other
 /// types will result in an `UnsupportedType` error.
 #[inline]
-pub fn to_variant_name<T>(value: &T) -> Result<&'static str, UnsupportedType>
+pub fn to_variant_name<T>(value: &T) -> StdResult<&'static str, UnsupportedType>
 where
   T: Serialize,
 {
@@ -58,6 +67,86 @@ where
 }
 
 
+/// Convert an enum variant into its numerical variant index.
+///
+/// Note that only enum variants may be converted here and all other
+/// types will result in an `UnsupportedType` error.
+#[inline]
+pub fn to_variant_index<T>(value: &T) -> StdResult<u32, UnsupportedType>
+where
+  T: Serialize,
+{
+  let mut serializer = IndexSerializer {};
+  value.serialize(&mut serializer)
+}
+
+
+/// The enum name, variant name, and variant index captured together
+/// by [`to_variant_info`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VariantInfo {
+  /// The name of the enum that the variant belongs to.
+  pub enum_name: &'static str,
+  /// The name of the variant itself.
+  pub variant_name: &'static str,
+  /// The numerical index of the variant within the enum.
+  pub variant_index: u32,
+}
+
+/// Convert an enum variant into a [`VariantInfo`] carrying the
+/// enclosing enum's name along with the variant's own name and
+/// index.
+///
+/// Note that only enum variants may be converted here and all other
+/// types will result in an `UnsupportedType` error.
+#[inline]
+pub fn to_variant_info<T>(value: &T) -> StdResult<VariantInfo, UnsupportedType>
+where
+  T: Serialize,
+{
+  let mut serializer = InfoSerializer {};
+  value.serialize(&mut serializer)
+}
+
+
+/// Convert a variant name back into an enum value.
+///
+/// This is the inverse of [`to_variant_name`]: it reconstructs a
+/// fieldless enum from the textual representation of one of its
+/// variants. Only unit variants are supported; attempting to
+/// deserialize a variant carrying data will fail with an `Error`.
+#[inline]
+pub fn from_variant_name<'de, T>(name: &'de str) -> Result<T>
+where
+  T: Deserialize<'de>,
+{
+  let mut deserializer = de::Deserializer::new(name);
+  T::deserialize(&mut deserializer)
+}
+
+
+/// Deserialize `T` from its textual representation.
+///
+/// This is the general purpose counterpart to [`from_variant_name`]:
+/// it drives `T::deserialize` over `input` and, unlike
+/// `from_variant_name`, additionally ensures that the whole string
+/// was consumed in the process, failing with an `Error` carrying a
+/// `TrailingData` code if anything is left over.
+#[inline]
+pub fn from_str<'de, T>(input: &'de str) -> Result<T>
+where
+  T: Deserialize<'de>,
+{
+  let mut deserializer = de::Deserializer::new(input);
+  let value = T::deserialize(&mut deserializer)?;
+  if deserializer.input.is_empty() {
+    Ok(value)
+  } else {
+    Err(ErrorCode::TrailingData(deserializer.input.to_owned()).into())
+  }
+}
+
+
 /// A serializer for tuple enum variants.
 struct TupleVariantSerializer(&'static str);
 
@@ -66,7 +155,7 @@ impl SerializeTupleVariant for TupleVariantSerializer {
   type Error = UnsupportedType;
 
   #[inline]
-  fn serialize_field<T>(&mut self, _value: &T) -> Result<(), Self::Error>
+  fn serialize_field<T>(&mut self, _value: &T) -> StdResult<(), Self::Error>
   where
     T: Serialize + ?Sized,
   {
@@ -74,7 +163,7 @@ impl SerializeTupleVariant for TupleVariantSerializer {
   }
 
   #[inline]
-  fn end(self) -> Result<Self::Ok, Self::Error> {
+  fn end(self) -> SerializationResult {
     Ok(self.0)
   }
 }
@@ -87,7 +176,7 @@ impl SerializeStructVariant for StructVariantSerializer {
   type Error = UnsupportedType;
 
   #[inline]
-  fn serialize_field<T>(&mut self, _key: &'static str, _value: &T) -> Result<(), Self::Error>
+  fn serialize_field<T>(&mut self, _key: &'static str, _value: &T) -> StdResult<(), Self::Error>
   where
     T: ?Sized + Serialize,
   {
@@ -95,17 +184,169 @@ impl SerializeStructVariant for StructVariantSerializer {
   }
 
   #[inline]
-  fn end(self) -> Result<Self::Ok, Self::Error> {
+  fn end(self) -> SerializationResult {
     Ok(self.0)
   }
 }
 
 
+/// Implement the `serde::Serializer` methods that a serializer which
+/// only ever extracts information about an enum variant can never
+/// support, i.e. every method other than the handful dealing with
+/// enum variants (and `serialize_some`, which just recurses).
+/// `serialize_newtype_struct` is not covered either and is
+/// implemented by each serializer itself.
+///
+/// This is shared by [`Serializer`], [`IndexSerializer`], and
+/// [`InfoSerializer`] so that a future addition to
+/// `serde::Serializer` only needs to be patched in one place instead
+/// of hand-copied into every variant-extracting serializer.
+macro_rules! impl_unsupported_serializer {
+  ($result:ty) => {
+    #[inline]
+    fn serialize_bool(self, _v: bool) -> $result {
+      Err(Self::Error::custom("serialize_bool"))
+    }
+
+    #[inline]
+    fn serialize_i8(self, _v: i8) -> $result {
+      Err(Self::Error::custom("serialize_i8"))
+    }
+
+    #[inline]
+    fn serialize_i16(self, _v: i16) -> $result {
+      Err(Self::Error::custom("serialize_i16"))
+    }
+
+    #[inline]
+    fn serialize_i32(self, _v: i32) -> $result {
+      Err(Self::Error::custom("serialize_i32"))
+    }
+
+    #[inline]
+    fn serialize_i64(self, _v: i64) -> $result {
+      Err(Self::Error::custom("serialize_i64"))
+    }
+
+    #[inline]
+    fn serialize_u8(self, _v: u8) -> $result {
+      Err(Self::Error::custom("serialize_u8"))
+    }
+
+    #[inline]
+    fn serialize_u16(self, _v: u16) -> $result {
+      Err(Self::Error::custom("serialize_u16"))
+    }
+
+    #[inline]
+    fn serialize_u32(self, _v: u32) -> $result {
+      Err(Self::Error::custom("serialize_u32"))
+    }
+
+    #[inline]
+    fn serialize_u64(self, _v: u64) -> $result {
+      Err(Self::Error::custom("serialize_u64"))
+    }
+
+    #[inline]
+    fn serialize_f32(self, _v: f32) -> $result {
+      Err(Self::Error::custom("serialize_f32"))
+    }
+
+    #[inline]
+    fn serialize_f64(self, _v: f64) -> $result {
+      Err(Self::Error::custom("serialize_f64"))
+    }
+
+    #[inline]
+    fn serialize_char(self, _v: char) -> $result {
+      Err(Self::Error::custom("serialize_char"))
+    }
+
+    #[inline]
+    fn serialize_str(self, _v: &str) -> $result {
+      Err(Self::Error::custom("serialize_str"))
+    }
+
+    #[inline]
+    fn serialize_bytes(self, _v: &[u8]) -> $result {
+      Err(Self::Error::custom("serialize_bytes"))
+    }
+
+    #[inline]
+    fn serialize_none(self) -> $result {
+      Err(Self::Error::custom("serialize_none"))
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> $result
+    where
+      T: ?Sized + Serialize,
+    {
+      value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> $result {
+      Err(Self::Error::custom("serialize_unit"))
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _name: &'static str) -> $result {
+      Err(Self::Error::custom("serialize_unit_struct"))
+    }
+
+    #[inline]
+    fn serialize_seq(self, _len: Option<usize>) -> StdResult<Self::SerializeSeq, Self::Error> {
+      Err(Self::Error::custom("serialize_seq"))
+    }
+
+    #[inline]
+    fn serialize_tuple(self, _len: usize) -> StdResult<Self::SerializeTuple, Self::Error> {
+      Err(Self::Error::custom("serialize_tuple"))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+      self,
+      _name: &'static str,
+      _len: usize,
+    ) -> StdResult<Self::SerializeTupleStruct, Self::Error> {
+      Err(Self::Error::custom("serialize_tuple_struct"))
+    }
+
+    #[inline]
+    fn serialize_map(self, _len: Option<usize>) -> StdResult<Self::SerializeMap, Self::Error> {
+      Err(Self::Error::custom("serialize_map"))
+    }
+
+    #[inline]
+    fn serialize_struct(
+      self,
+      _name: &'static str,
+      _len: usize,
+    ) -> StdResult<Self::SerializeStruct, Self::Error> {
+      Err(Self::Error::custom("serialize_struct"))
+    }
+
+    #[inline]
+    fn collect_str<T>(self, _value: &T) -> $result
+    where
+      T: ?Sized,
+    {
+      Err(Self::Error::custom("collect_str"))
+    }
+  };
+}
+
+
 /// A serde serializer that converts an enum variant into the variant's
 /// name.
 struct Serializer {}
 
-impl<'a> SerdeSerializer for &'a mut Serializer {
+type SerializationResult = StdResult<&'static str, UnsupportedType>;
+
+impl SerdeSerializer for &mut Serializer {
   type Ok = &'static str;
   type Error = UnsupportedType;
 
@@ -117,196 +358,322 @@ impl<'a> SerdeSerializer for &'a mut Serializer {
   type SerializeStruct = Impossible<Self::Ok, Self::Error>;
   type SerializeStructVariant = StructVariantSerializer;
 
+  impl_unsupported_serializer!(SerializationResult);
+
   #[inline]
-  fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_bool"))
+  fn serialize_unit_variant(
+    self,
+    _name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+  ) -> SerializationResult {
+    Ok(variant)
   }
 
   #[inline]
-  fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_i8"))
+  fn serialize_newtype_struct<T>(
+    self,
+    name: &'static str,
+    _value: &T,
+  ) -> SerializationResult
+  where
+    T: ?Sized + Serialize,
+  {
+    Ok(name)
+  }
+
+  #[inline]
+  fn serialize_newtype_variant<T>(
+    self,
+    _name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+    _value: &T,
+  ) -> SerializationResult
+  where
+    T: ?Sized + Serialize,
+  {
+    Ok(variant)
   }
 
   #[inline]
-  fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_i16"))
+  fn serialize_tuple_variant(
+    self,
+    _name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+    _len: usize,
+  ) -> StdResult<Self::SerializeTupleVariant, Self::Error> {
+    Ok(TupleVariantSerializer(variant))
   }
 
   #[inline]
-  fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_i32"))
+  fn serialize_struct_variant(
+    self,
+    _name: &'static str,
+    _variant_index: u32,
+    variant: &'static str,
+    _len: usize,
+  ) -> StdResult<Self::SerializeStructVariant, Self::Error> {
+    Ok(StructVariantSerializer(variant))
   }
+}
+
+
+/// A serializer for tuple enum variants that only cares about the
+/// variant's index.
+struct IndexTupleVariantSerializer(u32);
+
+impl SerializeTupleVariant for IndexTupleVariantSerializer {
+  type Ok = u32;
+  type Error = UnsupportedType;
 
   #[inline]
-  fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_i64"))
+  fn serialize_field<T>(&mut self, _value: &T) -> StdResult<(), Self::Error>
+  where
+    T: Serialize + ?Sized,
+  {
+    Ok(())
   }
 
   #[inline]
-  fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_u8"))
+  fn end(self) -> IndexResult {
+    Ok(self.0)
   }
+}
+
+/// A serializer for struct enum variants that only cares about the
+/// variant's index.
+struct IndexStructVariantSerializer(u32);
+
+impl SerializeStructVariant for IndexStructVariantSerializer {
+  type Ok = u32;
+  type Error = UnsupportedType;
 
   #[inline]
-  fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_u16"))
+  fn serialize_field<T>(&mut self, _key: &'static str, _value: &T) -> StdResult<(), Self::Error>
+  where
+    T: ?Sized + Serialize,
+  {
+    Ok(())
   }
 
   #[inline]
-  fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_u32"))
+  fn end(self) -> IndexResult {
+    Ok(self.0)
   }
+}
+
+
+/// A serde serializer that converts an enum variant into the
+/// variant's numerical index.
+struct IndexSerializer {}
+
+type IndexResult = StdResult<u32, UnsupportedType>;
+
+impl SerdeSerializer for &mut IndexSerializer {
+  type Ok = u32;
+  type Error = UnsupportedType;
+
+  type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+  type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+  type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+  type SerializeTupleVariant = IndexTupleVariantSerializer;
+  type SerializeMap = Impossible<Self::Ok, Self::Error>;
+  type SerializeStruct = Impossible<Self::Ok, Self::Error>;
+  type SerializeStructVariant = IndexStructVariantSerializer;
+
+  impl_unsupported_serializer!(IndexResult);
 
   #[inline]
-  fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_u64"))
+  fn serialize_unit_variant(
+    self,
+    _name: &'static str,
+    variant_index: u32,
+    _variant: &'static str,
+  ) -> IndexResult {
+    Ok(variant_index)
   }
 
   #[inline]
-  fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_f32"))
+  fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> IndexResult
+  where
+    T: ?Sized + Serialize,
+  {
+    Err(Self::Error::custom("serialize_newtype_struct"))
   }
 
   #[inline]
-  fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_f64"))
+  fn serialize_newtype_variant<T>(
+    self,
+    _name: &'static str,
+    variant_index: u32,
+    _variant: &'static str,
+    _value: &T,
+  ) -> IndexResult
+  where
+    T: ?Sized + Serialize,
+  {
+    Ok(variant_index)
   }
 
   #[inline]
-  fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_char"))
+  fn serialize_tuple_variant(
+    self,
+    _name: &'static str,
+    variant_index: u32,
+    _variant: &'static str,
+    _len: usize,
+  ) -> StdResult<Self::SerializeTupleVariant, Self::Error> {
+    Ok(IndexTupleVariantSerializer(variant_index))
   }
 
   #[inline]
-  fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_str"))
+  fn serialize_struct_variant(
+    self,
+    _name: &'static str,
+    variant_index: u32,
+    _variant: &'static str,
+    _len: usize,
+  ) -> StdResult<Self::SerializeStructVariant, Self::Error> {
+    Ok(IndexStructVariantSerializer(variant_index))
   }
+}
+
+
+/// A serializer for tuple enum variants that captures the enum name,
+/// variant name, and variant index together.
+struct InfoTupleVariantSerializer(VariantInfo);
+
+impl SerializeTupleVariant for InfoTupleVariantSerializer {
+  type Ok = VariantInfo;
+  type Error = UnsupportedType;
 
   #[inline]
-  fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_bytes"))
+  fn serialize_field<T>(&mut self, _value: &T) -> StdResult<(), Self::Error>
+  where
+    T: Serialize + ?Sized,
+  {
+    Ok(())
   }
 
   #[inline]
-  fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_none"))
+  fn end(self) -> InfoResult {
+    Ok(self.0)
   }
+}
+
+/// A serializer for struct enum variants that captures the enum
+/// name, variant name, and variant index together.
+struct InfoStructVariantSerializer(VariantInfo);
+
+impl SerializeStructVariant for InfoStructVariantSerializer {
+  type Ok = VariantInfo;
+  type Error = UnsupportedType;
 
   #[inline]
-  fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+  fn serialize_field<T>(&mut self, _key: &'static str, _value: &T) -> StdResult<(), Self::Error>
   where
     T: ?Sized + Serialize,
   {
-    value.serialize(self)
+    Ok(())
   }
 
   #[inline]
-  fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_unit"))
+  fn end(self) -> InfoResult {
+    Ok(self.0)
   }
+}
 
-  #[inline]
-  fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
-    Err(Self::Error::custom("serialize_unit_struct"))
-  }
+
+/// A serde serializer that converts an enum variant into a
+/// [`VariantInfo`].
+struct InfoSerializer {}
+
+type InfoResult = StdResult<VariantInfo, UnsupportedType>;
+
+impl SerdeSerializer for &mut InfoSerializer {
+  type Ok = VariantInfo;
+  type Error = UnsupportedType;
+
+  type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+  type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+  type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+  type SerializeTupleVariant = InfoTupleVariantSerializer;
+  type SerializeMap = Impossible<Self::Ok, Self::Error>;
+  type SerializeStruct = Impossible<Self::Ok, Self::Error>;
+  type SerializeStructVariant = InfoStructVariantSerializer;
+
+  impl_unsupported_serializer!(InfoResult);
 
   #[inline]
   fn serialize_unit_variant(
     self,
-    _name: &'static str,
-    _variant_index: u32,
+    name: &'static str,
+    variant_index: u32,
     variant: &'static str,
-  ) -> Result<Self::Ok, Self::Error> {
-    Ok(variant)
+  ) -> InfoResult {
+    Ok(VariantInfo {
+      enum_name: name,
+      variant_name: variant,
+      variant_index,
+    })
   }
 
   #[inline]
-  fn serialize_newtype_struct<T>(
-    self,
-    name: &'static str,
-    _value: &T,
-  ) -> Result<Self::Ok, Self::Error>
+  fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> InfoResult
   where
     T: ?Sized + Serialize,
   {
-    Ok(name)
+    Err(Self::Error::custom("serialize_newtype_struct"))
   }
 
   #[inline]
   fn serialize_newtype_variant<T>(
     self,
-    _name: &'static str,
-    _variant_index: u32,
+    name: &'static str,
+    variant_index: u32,
     variant: &'static str,
     _value: &T,
-  ) -> Result<Self::Ok, Self::Error>
+  ) -> InfoResult
   where
     T: ?Sized + Serialize,
   {
-    Ok(variant)
-  }
-
-  #[inline]
-  fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
-    Err(Self::Error::custom("serialize_seq"))
-  }
-
-  #[inline]
-  fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-    Err(Self::Error::custom("serialize_tuple"))
-  }
-
-  #[inline]
-  fn serialize_tuple_struct(
-    self,
-    _name: &'static str,
-    _len: usize,
-  ) -> Result<Self::SerializeTupleStruct, Self::Error> {
-    Err(Self::Error::custom("serialize_tuple_struct"))
+    Ok(VariantInfo {
+      enum_name: name,
+      variant_name: variant,
+      variant_index,
+    })
   }
 
   #[inline]
   fn serialize_tuple_variant(
     self,
-    _name: &'static str,
-    _variant_index: u32,
+    name: &'static str,
+    variant_index: u32,
     variant: &'static str,
     _len: usize,
-  ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-    Ok(TupleVariantSerializer(variant))
-  }
-
-  #[inline]
-  fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-    Err(Self::Error::custom("serialize_map"))
-  }
-
-  #[inline]
-  fn serialize_struct(
-    self,
-    _name: &'static str,
-    _len: usize,
-  ) -> Result<Self::SerializeStruct, Self::Error> {
-    Err(Self::Error::custom("serialize_struct"))
+  ) -> StdResult<Self::SerializeTupleVariant, Self::Error> {
+    Ok(InfoTupleVariantSerializer(VariantInfo {
+      enum_name: name,
+      variant_name: variant,
+      variant_index,
+    }))
   }
 
   #[inline]
   fn serialize_struct_variant(
     self,
-    _name: &'static str,
-    _variant_index: u32,
+    name: &'static str,
+    variant_index: u32,
     variant: &'static str,
     _len: usize,
-  ) -> Result<Self::SerializeStructVariant, Self::Error> {
-    Ok(StructVariantSerializer(variant))
-  }
-
-  #[inline]
-  fn collect_str<T>(self, _value: &T) -> Result<Self::Ok, Self::Error>
-  where
-    T: ?Sized,
-  {
-    Err(Self::Error::custom("collect_str"))
+  ) -> StdResult<Self::SerializeStructVariant, Self::Error> {
+    Ok(InfoStructVariantSerializer(VariantInfo {
+      enum_name: name,
+      variant_name: variant,
+      variant_index,
+    }))
   }
 }
 
@@ -380,4 +747,168 @@ mod tests {
     assert_eq!(to_variant_name(&Foo::Baz { i: 0 }).unwrap(), "Baz");
     assert_eq!(to_variant_name(&Foo::Var { i: 0 }).unwrap(), "VAR");
   }
+
+  #[test]
+  fn variant_indices() {
+    #[derive(Serialize)]
+    enum Foo {
+      Var1,
+      Var2(u32),
+      Var3((), ()),
+      Var4 { i: i32 },
+    }
+
+    assert_eq!(to_variant_index(&Foo::Var1).unwrap(), 0);
+    assert_eq!(to_variant_index(&Foo::Var2(42)).unwrap(), 1);
+    assert_eq!(to_variant_index(&Foo::Var3((), ())).unwrap(), 2);
+    assert_eq!(to_variant_index(&Foo::Var4 { i: 0 }).unwrap(), 3);
+  }
+
+  #[test]
+  fn variant_info() {
+    #[derive(Serialize)]
+    enum Foo {
+      Var1,
+      #[serde(rename = "VAR2")]
+      Var2(u32),
+    }
+
+    assert_eq!(
+      to_variant_info(&Foo::Var1).unwrap(),
+      VariantInfo {
+        enum_name: "Foo",
+        variant_name: "Var1",
+        variant_index: 0,
+      }
+    );
+    assert_eq!(
+      to_variant_info(&Foo::Var2(42)).unwrap(),
+      VariantInfo {
+        enum_name: "Foo",
+        variant_name: "VAR2",
+        variant_index: 1,
+      }
+    );
+  }
+
+  /// Check that we can reconstruct a fieldless enum from its variant
+  /// name.
+  #[test]
+  fn unit_variant_from_name() {
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    enum Foo {
+      Var1,
+      #[serde(rename = "VAR2")]
+      Var2,
+    }
+
+    assert_eq!(from_variant_name::<Foo>("Var1").unwrap(), Foo::Var1);
+    assert_eq!(from_variant_name::<Foo>("VAR2").unwrap(), Foo::Var2);
+    assert!(from_variant_name::<Foo>("not-a-variant").is_err());
+  }
+
+  /// Check that scalar types can be decoded from their textual
+  /// representation.
+  #[test]
+  fn scalars_from_str() {
+    assert!(from_variant_name::<bool>("true").unwrap());
+    assert!(!from_variant_name::<bool>("false").unwrap());
+    assert!(from_variant_name::<bool>("nope").is_err());
+
+    assert_eq!(from_variant_name::<i8>("-42").unwrap(), -42i8);
+    assert_eq!(from_variant_name::<u32>("1337").unwrap(), 1337u32);
+    assert_eq!(from_variant_name::<i128>("-1").unwrap(), -1i128);
+    assert_eq!(from_variant_name::<u128>("1").unwrap(), 1u128);
+    assert!(from_variant_name::<u8>("256").is_err());
+
+    assert_eq!(from_variant_name::<f64>("3.5").unwrap(), 3.5);
+    assert!(from_variant_name::<f32>("not-a-float").is_err());
+
+    assert_eq!(from_variant_name::<char>("x").unwrap(), 'x');
+    assert!(from_variant_name::<char>("xy").is_err());
+  }
+
+  /// Check that untagged enums, which rely on `deserialize_any`, and
+  /// `IgnoredAny` both work. This exercises the self-describing path
+  /// through a hand-written untagged enum rather than
+  /// `serde_json::Value`, since this crate has no `serde_json`
+  /// dependency to test against.
+  #[test]
+  fn any_and_ignored_any() {
+    use serde::de::IgnoredAny;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(untagged)]
+    enum Value {
+      Bool(bool),
+      Int(i64),
+      Str(String),
+    }
+
+    assert_eq!(from_variant_name::<Value>("true").unwrap(), Value::Bool(true));
+    assert_eq!(from_variant_name::<Value>("42").unwrap(), Value::Int(42));
+    assert_eq!(
+      from_variant_name::<Value>("hello").unwrap(),
+      Value::Str("hello".to_owned())
+    );
+
+    let _ = from_variant_name::<IgnoredAny>("whatever").unwrap();
+  }
+
+  /// Check that a `Deserializer` can be converted into an
+  /// `IntoDeserializer`, as is necessary when driving a `MapAccess`
+  /// over a collection of variant-name strings.
+  #[test]
+  fn into_deserializer() {
+    use serde::de::IntoDeserializer;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Foo {
+      Var1,
+      #[serde(rename = "VAR2")]
+      Var2,
+    }
+
+    let deserializer = de::Deserializer::new("Var1");
+    let value = Foo::deserialize(deserializer.into_deserializer()).unwrap();
+    assert_eq!(value, Foo::Var1);
+
+    let mut deserializer = de::Deserializer::new("VAR2");
+    let value = Foo::deserialize((&mut deserializer).into_deserializer()).unwrap();
+    assert_eq!(value, Foo::Var2);
+  }
+
+  /// Check that an empty input decodes to `None` and otherwise lets
+  /// the inner type drive decoding.
+  #[test]
+  fn option_handling() {
+    assert_eq!(from_variant_name::<Option<u32>>("").unwrap(), None);
+    assert_eq!(from_variant_name::<Option<u32>>("1337").unwrap(), Some(1337));
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Foo {
+      Var1,
+    }
+
+    assert_eq!(from_variant_name::<Option<Foo>>("").unwrap(), None);
+    assert_eq!(
+      from_variant_name::<Option<Foo>>("Var1").unwrap(),
+      Some(Foo::Var1)
+    );
+  }
+
+  /// Check that `from_str` decodes the same values as
+  /// `from_variant_name`.
+  #[test]
+  fn from_str_round_trip() {
+    assert_eq!(from_str::<u32>("1337").unwrap(), 1337);
+    assert!(from_str::<bool>("true").unwrap());
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Foo {
+      Var1,
+    }
+
+    assert_eq!(from_str::<Foo>("Var1").unwrap(), Foo::Var1);
+  }
 }